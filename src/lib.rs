@@ -16,20 +16,89 @@
 
 #![doc = include_str!("../README.md")]
 
+use std::cell::{Cell, RefCell};
 use std::sync::RwLock;
 
 use log::{Level, Log, Record};
 
-/// The list of captured logs.
+/// The list of captured logs, used when running in global capture mode.
 static LOGS: RwLock<Vec<CapturedLog>> = RwLock::new(Vec::new());
 
+thread_local! {
+    /// The list of captured logs for the current thread, used when running
+    /// in thread-local capture mode.
+    static THREAD_LOGS: RefCell<Vec<CapturedLog>> = const { RefCell::new(Vec::new()) };
+
+    /// A stack of contextual key-value frames pushed by
+    /// [`LogTester::with_context`]. The top frame, if any, is stamped onto
+    /// every log captured on this thread.
+    static CONTEXT_STACK: RefCell<Vec<Vec<(String, String)>>> = const { RefCell::new(Vec::new()) };
+
+    /// Whether the calling thread is currently capturing into its own
+    /// [`THREAD_LOGS`] buffer (`true`) or into the shared [`LOGS`] buffer
+    /// (`false`). Deliberately thread-local rather than a shared flag: a
+    /// test thread calling `start()`/`start_thread_local()` must not be
+    /// able to flip the mode out from under every other thread running
+    /// `cargo test` concurrently.
+    static THREAD_LOCAL_MODE: Cell<bool> = const { Cell::new(false) };
+
+    /// The filter directives set on this thread by
+    /// [`LogTester::start_with_filter`], sorted by descending path length
+    /// so that the first matching directive is always the most specific
+    /// one. Empty means "capture everything", matching the behaviour of
+    /// [`LogTester::start`]. Thread-local for the same reason as
+    /// [`THREAD_LOCAL_MODE`]: directives set up by one test thread must not
+    /// leak into another thread's filtering decisions.
+    static DIRECTIVES: RefCell<Vec<Directive>> = const { RefCell::new(Vec::new()) };
+}
+
 static INIT: std::sync::Once = std::sync::Once::new();
 
+/// A single `RUST_LOG`-style filter directive, e.g. the `mycrate::net=trace`
+/// in `mycrate=debug,mycrate::net=trace,warn`.
+#[derive(Debug, Clone)]
+struct Directive {
+    /// The module path prefix this directive applies to, or `None` if it is
+    /// a bare level that sets the global default.
+    path: Option<String>,
+    /// The maximum level this directive captures.
+    level: log::LevelFilter,
+}
+
+impl Directive {
+    /// Parses a single directive such as `mycrate::net=trace`, `warn` or
+    /// `mycrate`. Returns `None` for an empty directive (e.g. from a
+    /// trailing comma).
+    fn parse(spec: &str) -> Option<Self> {
+        let spec = spec.trim();
+        if spec.is_empty() {
+            return None;
+        }
+        if let Some((path, level)) = spec.split_once('=') {
+            let path = path.trim();
+            let level = level.trim().parse().ok()?;
+            let path = if path.is_empty() {
+                None
+            } else {
+                Some(path.to_string())
+            };
+            Some(Directive { path, level })
+        } else if let Ok(level) = spec.parse() {
+            Some(Directive { path: None, level })
+        } else {
+            Some(Directive {
+                path: Some(spec.to_string()),
+                level: log::LevelFilter::Trace,
+            })
+        }
+    }
+}
+
 /// The logger
 pub struct LogTester;
 
 /// A log that was captured
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct CapturedLog {
     /// The formatted log message.
     pub body: String,
@@ -37,12 +106,90 @@ pub struct CapturedLog {
     pub level: Level,
     /// The target.
     pub target: String,
+    /// When the record was captured, for ordering and relative display.
+    pub timestamp: std::time::Instant,
+    /// When the record was captured, as a wall-clock time.
+    pub system_time: std::time::SystemTime,
+    /// The structured key-value fields attached to the record, e.g. via
+    /// `log::info!(user_id = 42; "login")`.
+    #[cfg(feature = "kv")]
+    pub fields: Vec<(String, String)>,
+    /// The contextual key-value pairs in effect when this log was captured,
+    /// set by [`LogTester::with_context`].
+    pub context: Vec<(String, String)>,
+}
+
+impl CapturedLog {
+    /// Returns the value of the structured field `key`, if the record carried one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use log_tester::LogTester;
+    /// use log::Level;
+    ///
+    /// LogTester::start();
+    /// log::info!(user_id = 42; "login");
+    /// let log = LogTester::find(|log| log.level == Level::Info).unwrap();
+    /// assert_eq!(log.field("user_id"), Some("42"));
+    /// assert_eq!(log.field("missing_key"), None);
+    /// ```
+    #[cfg(feature = "kv")]
+    pub fn field(&self, key: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Collects a record's structured key-value fields into a `Vec<(String, String)>`.
+#[cfg(feature = "kv")]
+struct FieldVisitor<'a> {
+    fields: &'a mut Vec<(String, String)>,
+}
+
+#[cfg(feature = "kv")]
+impl<'kvs> log::kv::VisitSource<'kvs> for FieldVisitor<'_> {
+    fn visit_pair(
+        &mut self,
+        key: log::kv::Key<'kvs>,
+        value: log::kv::Value<'kvs>,
+    ) -> Result<(), log::kv::Error> {
+        self.fields.push((key.to_string(), value.to_string()));
+        Ok(())
+    }
+}
+
+/// A handle returned by [`LogTester::with_context`]
+///
+/// For as long as the handle is alive, every log captured on the thread
+/// that created it is stamped with the handle's context pairs (see
+/// [`CapturedLog::context`]). Dropping the handle restores whatever
+/// context, if any, was in effect before it was created.
+pub struct ContextHandle {
+    _private: (),
+}
+
+impl Drop for ContextHandle {
+    fn drop(&mut self) {
+        CONTEXT_STACK.with(|stack| {
+            stack.borrow_mut().pop();
+        });
+    }
 }
 
 impl LogTester {
     /// Start the logger
     ///
-    /// This should only be called once
+    /// All logs captured on the calling thread are pushed into a single
+    /// buffer shared by every thread. This should only be called once per
+    /// thread.
+    ///
+    /// Whether capture is global or per-thread is a per-thread setting (see
+    /// [`LogTester::start_thread_local`]): each thread picks its own mode
+    /// by calling the matching `start*` function, and that choice has no
+    /// effect on any other thread.
     ///
     /// # Examples
     ///
@@ -52,10 +199,115 @@ impl LogTester {
     /// LogTester::start();
     /// ```
     pub fn start() {
+        Self::init();
+        THREAD_LOCAL_MODE.with(|mode| mode.set(false));
+        Self::set_directives(Vec::new());
+    }
+
+    /// Start the logger in thread-local capture mode
+    ///
+    /// Each thread accumulates its own logs in its own buffer, so
+    /// [`LogTester::contains`], [`LogTester::len`], [`LogTester::clear`] and
+    /// the [`Display`](std::fmt::Display) impl only ever see the entries
+    /// captured on the calling thread. This is what you want when running
+    /// tests in parallel, since threads can no longer clobber each other's
+    /// captured logs.
+    ///
+    /// This should only be called once per thread. Thread-local vs. global
+    /// capture is a per-thread setting: calling this on one thread has no
+    /// effect on the mode any other thread is using.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use log_tester::LogTester;
+    ///
+    /// LogTester::start_thread_local();
+    /// ```
+    pub fn start_thread_local() {
+        Self::init();
+        THREAD_LOCAL_MODE.with(|mode| mode.set(true));
+        Self::set_directives(Vec::new());
+    }
+
+    /// Start the logger with an `env_logger`-style filter, in thread-local
+    /// capture mode
+    ///
+    /// `spec` is a comma-separated list of directives, each of which is an
+    /// optional module path prefix and an optional `=level`, e.g.
+    /// `mycrate=debug,mycrate::net=trace,warn`. A directive with no path
+    /// sets the default level for targets that no other directive matches;
+    /// a directive with no level defaults to [`log::LevelFilter::Trace`].
+    /// Only records whose target starts with the most specific matching
+    /// directive's path, and whose level is at or above that directive's
+    /// level, are captured.
+    ///
+    /// Like [`LogTester::start_thread_local`], this captures into the
+    /// calling thread's own buffer and the directives themselves are also
+    /// thread-local, so two threads can run different filters at the same
+    /// time without interfering with each other. This should only be
+    /// called once per thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use log_tester::LogTester;
+    ///
+    /// LogTester::start_with_filter("mycrate::net=trace,warn");
+    /// ```
+    pub fn start_with_filter(spec: &str) {
+        Self::init();
+        THREAD_LOCAL_MODE.with(|mode| mode.set(true));
+        let mut directives: Vec<Directive> = spec.split(',').filter_map(Directive::parse).collect();
+        directives.sort_by_key(|directive| {
+            std::cmp::Reverse(directive.path.as_deref().map_or(0, str::len))
+        });
+        Self::set_directives(directives);
+    }
+
+    /// Registers the logger with the `log` crate, if it hasn't been already.
+    fn init() {
         INIT.call_once(|| log::set_logger(&LogTester).expect("Failed to start the logger"));
         log::set_max_level(log::LevelFilter::Trace);
     }
 
+    /// Replaces the calling thread's stored filter directives.
+    fn set_directives(directives: Vec<Directive>) {
+        DIRECTIVES.with(|cell| *cell.borrow_mut() = directives);
+    }
+
+    /// Returns true if the calling thread is currently capturing into its
+    /// own per-thread buffer rather than the shared global buffer.
+    fn is_thread_local() -> bool {
+        THREAD_LOCAL_MODE.with(|mode| mode.get())
+    }
+
+    /// Returns the level at or below which records from `target` should be
+    /// captured on the calling thread, picking the most specific directive
+    /// whose path is a prefix of `target` (a directive with no path always
+    /// matches, and acts as the default).
+    ///
+    /// With no directives set (plain [`LogTester::start`]), everything is
+    /// captured. But once directives are set, a target matched by none of
+    /// them is dropped entirely, the same way `env_logger` silences crates
+    /// that aren't named in `RUST_LOG` unless a bare default level is given.
+    fn level_for(target: &str) -> log::LevelFilter {
+        DIRECTIVES.with(|cell| {
+            let directives = cell.borrow();
+            if directives.is_empty() {
+                return log::LevelFilter::Trace;
+            }
+            for directive in directives.iter() {
+                match &directive.path {
+                    Some(path) if target.starts_with(path.as_str()) => return directive.level,
+                    None => return directive.level,
+                    Some(_) => continue,
+                }
+            }
+            log::LevelFilter::Off
+        })
+    }
+
     /// Returns true if there is an entry with the given level that contains the given content
     ///
     /// # Examples
@@ -69,15 +321,268 @@ impl LogTester {
     /// assert!(LogTester::contains(log::Level::Info, "Hello, world!"));
     /// ```
     pub fn contains(level: Level, content: &str) -> bool {
-        let logs = LOGS
-            .read()
-            .expect("Failed to get the read lock on the logs");
-        for log in logs.iter() {
-            if log.level == level && log.body.contains(content) {
-                return true;
+        if Self::is_thread_local() {
+            THREAD_LOGS.with(|logs| {
+                logs.borrow()
+                    .iter()
+                    .any(|log| log.level == level && log.body.contains(content))
+            })
+        } else {
+            let logs = LOGS
+                .read()
+                .expect("Failed to get the read lock on the logs");
+            logs.iter()
+                .any(|log| log.level == level && log.body.contains(content))
+        }
+    }
+
+    /// Returns true if there is an entry with the given level that has a
+    /// structured field `key` equal to `value`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use log_tester::LogTester;
+    /// use log::Level;
+    ///
+    /// LogTester::start();
+    /// log::info!(user_id = 42; "login");
+    /// assert!(LogTester::contains_with_field(Level::Info, "user_id", "42"));
+    /// ```
+    #[cfg(feature = "kv")]
+    pub fn contains_with_field(level: Level, key: &str, value: &str) -> bool {
+        if Self::is_thread_local() {
+            THREAD_LOGS.with(|logs| {
+                logs.borrow()
+                    .iter()
+                    .any(|log| log.level == level && log.field(key) == Some(value))
+            })
+        } else {
+            let logs = LOGS
+                .read()
+                .expect("Failed to get the read lock on the logs");
+            logs.iter()
+                .any(|log| log.level == level && log.field(key) == Some(value))
+        }
+    }
+
+    /// Returns true if there is an entry with the given level whose body matches the given regex
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use log_tester::LogTester;
+    /// use log::Level;
+    ///
+    /// LogTester::start();
+    /// log::info!("request 42 took 7ms");
+    /// assert!(LogTester::contains_regex(Level::Info, r"request \d+ took \d+ms").unwrap());
+    /// ```
+    #[cfg(feature = "regex")]
+    pub fn contains_regex(level: Level, pattern: &str) -> Result<bool, regex::Error> {
+        let re = regex::Regex::new(pattern)?;
+        Ok(if Self::is_thread_local() {
+            THREAD_LOGS.with(|logs| {
+                logs.borrow()
+                    .iter()
+                    .any(|log| log.level == level && re.is_match(&log.body))
+            })
+        } else {
+            let logs = LOGS
+                .read()
+                .expect("Failed to get the read lock on the logs");
+            logs.iter()
+                .any(|log| log.level == level && re.is_match(&log.body))
+        })
+    }
+
+    /// Returns a clone of the first captured log matching the given predicate
+    ///
+    /// Returns an owned [`CapturedLog`] rather than a reference, so the
+    /// caller isn't left holding the internal lock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use log_tester::LogTester;
+    /// use log::Level;
+    ///
+    /// LogTester::start();
+    /// log::error!(target: "mycrate::net", "connection refused");
+    /// let log = LogTester::find(|log| log.level == Level::Error && log.target == "mycrate::net");
+    /// assert!(log.is_some());
+    /// ```
+    pub fn find<F: Fn(&CapturedLog) -> bool>(pred: F) -> Option<CapturedLog> {
+        if Self::is_thread_local() {
+            THREAD_LOGS.with(|logs| logs.borrow().iter().find(|log| pred(log)).cloned())
+        } else {
+            let logs = LOGS
+                .read()
+                .expect("Failed to get the read lock on the logs");
+            logs.iter().find(|log| pred(log)).cloned()
+        }
+    }
+
+    /// Asserts that at least one captured log matches the given predicate
+    ///
+    /// # Panics
+    ///
+    /// Panics if no captured log matches `pred`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use log_tester::LogTester;
+    /// use log::Level;
+    ///
+    /// LogTester::start();
+    /// log::warn!(target: "mycrate::net", "retrying");
+    /// LogTester::assert_matches(|log| log.level == Level::Warn && log.target == "mycrate::net");
+    /// ```
+    pub fn assert_matches<F: Fn(&CapturedLog) -> bool>(pred: F) {
+        assert!(
+            Self::find(pred).is_some(),
+            "No captured log matched the given predicate"
+        );
+    }
+
+    /// Tags every log captured on this thread, for the lifetime of the
+    /// returned handle, with the given context pairs
+    ///
+    /// Useful for scoping assertions to the logs emitted inside a single
+    /// logical operation, even when other code is logging concurrently on
+    /// other threads.
+    ///
+    /// Nested calls merge with, rather than replace, the enclosing context:
+    /// the new frame starts as a copy of the current one, and `pairs` is
+    /// applied on top of it (overwriting a key it shares with the parent,
+    /// adding any key it doesn't). This mirrors how rust-lightning's
+    /// `WithContext` logger wraps a base logger without discarding the
+    /// fields the base logger already attaches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use log_tester::LogTester;
+    /// use log::Level;
+    ///
+    /// LogTester::start();
+    /// {
+    ///     let _ctx = LogTester::with_context(&[("request_id", "42")]);
+    ///     log::info!("handling request");
+    /// }
+    /// assert!(LogTester::contains_in_context("request_id", "42", Level::Info, "handling request"));
+    /// ```
+    pub fn with_context(pairs: &[(&str, &str)]) -> ContextHandle {
+        let mut frame = Self::current_context();
+        for (key, value) in pairs {
+            let key = key.to_string();
+            let value = value.to_string();
+            match frame.iter_mut().find(|(k, _)| *k == key) {
+                Some(existing) => existing.1 = value,
+                None => frame.push((key, value)),
             }
         }
-        false
+        CONTEXT_STACK.with(|stack| stack.borrow_mut().push(frame));
+        ContextHandle { _private: () }
+    }
+
+    /// Returns the context pairs currently in effect on this thread, if any.
+    fn current_context() -> Vec<(String, String)> {
+        CONTEXT_STACK.with(|stack| stack.borrow().last().cloned().unwrap_or_default())
+    }
+
+    /// Returns true if there is an entry, captured while `context_key` was
+    /// set to `context_value` via [`LogTester::with_context`], with the
+    /// given level that contains the given content
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use log_tester::LogTester;
+    /// use log::Level;
+    ///
+    /// LogTester::start();
+    /// {
+    ///     let _ctx = LogTester::with_context(&[("request_id", "42")]);
+    ///     log::info!("handling request");
+    /// }
+    /// assert!(LogTester::contains_in_context("request_id", "42", Level::Info, "handling request"));
+    /// ```
+    pub fn contains_in_context(
+        context_key: &str,
+        context_value: &str,
+        level: Level,
+        content: &str,
+    ) -> bool {
+        let matches = |log: &CapturedLog| {
+            log.level == level
+                && log.body.contains(content)
+                && log
+                    .context
+                    .iter()
+                    .any(|(key, value)| key == context_key && value == context_value)
+        };
+        if Self::is_thread_local() {
+            THREAD_LOGS.with(|logs| logs.borrow().iter().any(matches))
+        } else {
+            let logs = LOGS
+                .read()
+                .expect("Failed to get the read lock on the logs");
+            logs.iter().any(matches)
+        }
+    }
+
+    /// Returns the captured logs in the order they were captured
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use log_tester::LogTester;
+    ///
+    /// LogTester::start();
+    /// log::info!("first");
+    /// log::info!("second");
+    /// let logs = LogTester::ordered();
+    /// assert_eq!(logs[0].body, "first");
+    /// assert_eq!(logs[1].body, "second");
+    /// ```
+    pub fn ordered() -> Vec<CapturedLog> {
+        if Self::is_thread_local() {
+            THREAD_LOGS.with(|logs| logs.borrow().clone())
+        } else {
+            LOGS.read()
+                .expect("Failed to get the read lock on the logs")
+                .clone()
+        }
+    }
+
+    /// Returns the captured logs whose timestamp falls within `[start, end]`
+    ///
+    /// Useful for asserting that a burst of logs (e.g. retries) happened
+    /// within a given time window.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use log_tester::LogTester;
+    /// use std::time::Instant;
+    ///
+    /// LogTester::start();
+    /// let start = Instant::now();
+    /// log::warn!("retry 1");
+    /// log::warn!("retry 2");
+    /// let end = Instant::now();
+    /// assert_eq!(LogTester::entries_between(start, end).len(), 2);
+    /// ```
+    pub fn entries_between(
+        start: std::time::Instant,
+        end: std::time::Instant,
+    ) -> Vec<CapturedLog> {
+        Self::ordered()
+            .into_iter()
+            .filter(|log| log.timestamp >= start && log.timestamp <= end)
+            .collect()
     }
 
     /// Returns the number of captured logs
@@ -93,9 +598,13 @@ impl LogTester {
     /// assert_eq!(LogTester::len(), 1);
     /// ```
     pub fn len() -> usize {
-        LOGS.read()
-            .expect("Failed to get the read lock on the logs")
-            .len()
+        if Self::is_thread_local() {
+            THREAD_LOGS.with(|logs| logs.borrow().len())
+        } else {
+            LOGS.read()
+                .expect("Failed to get the read lock on the logs")
+                .len()
+        }
     }
 
     /// Clears the captured logs
@@ -112,48 +621,96 @@ impl LogTester {
     /// assert_eq!(LogTester::len(), 0);
     /// ```
     pub fn clear() {
-        LOGS.write()
-            .expect("Failed to get the write lock on the logs")
-            .clear();
+        if Self::is_thread_local() {
+            THREAD_LOGS.with(|logs| logs.borrow_mut().clear());
+        } else {
+            LOGS.write()
+                .expect("Failed to get the write lock on the logs")
+                .clear();
+        }
     }
 }
 
 impl std::fmt::Display for CapturedLog {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.body)
+        write!(
+            f,
+            "[{} ago] {}",
+            format_elapsed(self.timestamp.elapsed()),
+            self.body
+        )
+    }
+}
+
+/// Renders a [`Duration`](std::time::Duration) the way `env_logger`'s
+/// `humantime` formatter renders timestamps: a compact, human-readable
+/// approximation rather than a raw number of nanoseconds.
+fn format_elapsed(elapsed: std::time::Duration) -> String {
+    let secs = elapsed.as_secs();
+    if secs == 0 {
+        format!("{}ms", elapsed.subsec_millis())
+    } else if secs < 60 {
+        format!("{}.{:03}s", secs, elapsed.subsec_millis())
+    } else {
+        format!("{}m{}s", secs / 60, secs % 60)
     }
 }
 
 impl std::fmt::Debug for LogTester {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let logs = LOGS
-            .read()
-            .expect("Failed to get the read lock on the logs");
-        write!(f, "{:?}", logs)
+        if Self::is_thread_local() {
+            THREAD_LOGS.with(|logs| write!(f, "{:?}", logs.borrow()))
+        } else {
+            let logs = LOGS
+                .read()
+                .expect("Failed to get the read lock on the logs");
+            write!(f, "{:?}", logs)
+        }
     }
 }
 
 impl std::fmt::Display for LogTester {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let logs = LOGS
-            .read()
-            .expect("Failed to get the read lock on the logs");
-        for log in logs.iter() {
-            writeln!(f, "{}", log)?;
+        if Self::is_thread_local() {
+            THREAD_LOGS.with(|logs| {
+                for log in logs.borrow().iter() {
+                    writeln!(f, "{}", log)?;
+                }
+                Ok(())
+            })
+        } else {
+            let logs = LOGS
+                .read()
+                .expect("Failed to get the read lock on the logs");
+            for log in logs.iter() {
+                writeln!(f, "{}", log)?;
+            }
+            Ok(())
         }
-        Ok(())
     }
 }
 
 impl Log for LogTester {
-    fn enabled(&self, _: &log::Metadata) -> bool {
-        true
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= Self::level_for(metadata.target())
     }
 
     fn log(&self, record: &Record) {
-        LOGS.write()
-            .expect("Failed to get the write lock on the logs")
-            .push(record.into());
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        if Self::is_thread_local() {
+            // Use `try_with` rather than `with`: at thread-exit time the
+            // thread-local may already be in the process of being torn
+            // down, and we would rather silently drop the record than
+            // panic out of a destructor (see env_logger's `log_tls_dtors`
+            // handling of the same issue).
+            let _ = THREAD_LOGS.try_with(|logs| logs.borrow_mut().push(record.into()));
+        } else {
+            LOGS.write()
+                .expect("Failed to get the write lock on the logs")
+                .push(record.into());
+        }
     }
 
     fn flush(&self) {}
@@ -161,10 +718,23 @@ impl Log for LogTester {
 
 impl From<&Record<'_>> for CapturedLog {
     fn from(record: &Record<'_>) -> Self {
+        #[cfg(feature = "kv")]
+        let fields = {
+            let mut fields = Vec::new();
+            let _ = record
+                .key_values()
+                .visit(&mut FieldVisitor { fields: &mut fields });
+            fields
+        };
         CapturedLog {
             body: record.args().to_string(),
             level: record.level(),
             target: record.target().to_string(),
+            timestamp: std::time::Instant::now(),
+            system_time: std::time::SystemTime::now(),
+            #[cfg(feature = "kv")]
+            fields,
+            context: LogTester::current_context(),
         }
     }
 }
@@ -176,7 +746,7 @@ mod tests {
 
     #[test]
     fn test_log() {
-        LogTester::start();
+        LogTester::start_thread_local();
         assert_eq!(LogTester::len(), 0);
         trace!("trace");
         debug!("debug");
@@ -194,7 +764,7 @@ mod tests {
 
     #[test]
     fn test_max_level() {
-        LogTester::start();
+        LogTester::start_thread_local();
         assert_eq!(log::max_level(), log::LevelFilter::Trace);
         for level in Level::iter() {
             assert!(log_enabled!(level));
@@ -203,13 +773,13 @@ mod tests {
 
     #[test]
     fn test_flush() {
-        LogTester::start();
+        LogTester::start_thread_local();
         log::logger().flush();
     }
 
     #[test]
     fn test_clear() {
-        LogTester::start();
+        LogTester::start_thread_local();
         trace!("trace");
         debug!("debug");
         info!("info");
@@ -222,7 +792,7 @@ mod tests {
 
     #[test]
     fn test_display() {
-        LogTester::start();
+        LogTester::start_thread_local();
         trace!("trace");
         debug!("debug");
         info!("info");
@@ -237,7 +807,7 @@ mod tests {
 
     #[test]
     fn test_debug() {
-        LogTester::start();
+        LogTester::start_thread_local();
         trace!("trace");
         debug!("debug");
         info!("info");
@@ -249,4 +819,216 @@ mod tests {
         assert!(format!("{:?}", LogTester).contains("warn"));
         assert!(format!("{:?}", LogTester).contains("error"));
     }
+
+    #[test]
+    fn test_thread_local() {
+        LogTester::start_thread_local();
+
+        let handle = std::thread::spawn(|| {
+            LogTester::start_thread_local();
+            assert_eq!(LogTester::len(), 0);
+            info!("from the other thread");
+            assert!(LogTester::contains(Level::Info, "from the other thread"));
+            LogTester::len()
+        });
+        let other_thread_len = handle.join().expect("Thread panicked");
+        assert_eq!(other_thread_len, 1);
+
+        // The main thread's buffer is unaffected by what the other thread logged.
+        assert!(!LogTester::contains(Level::Info, "from the other thread"));
+    }
+
+    #[test]
+    fn test_global_capture_mode() {
+        // This is the only test in the suite exercising LogTester::start()'s
+        // shared-buffer path; every other test uses start_thread_local() to
+        // avoid racing on the shared LOGS buffer, so this one is safe to run
+        // alongside them under cargo test's default parallel runner.
+        LogTester::start();
+        LogTester::clear();
+        info!("from global mode");
+        assert!(LogTester::contains(Level::Info, "from global mode"));
+        assert_eq!(LogTester::len(), 1);
+    }
+
+    #[test]
+    fn test_filter() {
+        LogTester::start_with_filter("log_tester::tests::sub=debug,warn");
+        LogTester::clear();
+
+        log::warn!("global warn");
+        log::info!("global info, filtered out by the default level");
+        log::log!(target: "log_tester::tests::sub", Level::Debug, "sub debug");
+        log::log!(target: "log_tester::tests::sub", Level::Trace, "sub trace, filtered out");
+
+        assert!(LogTester::contains(Level::Warn, "global warn"));
+        assert!(!LogTester::contains(Level::Info, "global info"));
+        assert!(LogTester::contains(Level::Debug, "sub debug"));
+        assert!(!LogTester::contains(Level::Trace, "sub trace"));
+        assert_eq!(LogTester::len(), 2);
+    }
+
+    #[test]
+    fn test_filter_drops_unmatched_targets() {
+        LogTester::start_with_filter("log_tester::tests::sub=debug");
+        LogTester::clear();
+
+        log::log!(target: "log_tester::tests::sub", Level::Debug, "sub debug");
+        log::log!(target: "noisy_dep", Level::Trace, "noisy dep, not named in the filter");
+
+        assert!(LogTester::contains(Level::Debug, "sub debug"));
+        assert!(!LogTester::contains(Level::Trace, "noisy dep"));
+        assert_eq!(LogTester::len(), 1);
+    }
+
+    #[cfg(feature = "kv")]
+    #[test]
+    fn test_fields() {
+        LogTester::start_thread_local();
+        info!(user_id = 42; "login");
+        assert!(LogTester::contains_with_field(Level::Info, "user_id", "42"));
+        assert!(!LogTester::contains_with_field(Level::Info, "user_id", "43"));
+        assert!(!LogTester::contains_with_field(
+            Level::Info,
+            "other_key",
+            "42"
+        ));
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_contains_regex() {
+        LogTester::start_thread_local();
+        info!("request 42 took 7ms");
+        assert!(LogTester::contains_regex(Level::Info, r"request \d+ took \d+ms").unwrap());
+        assert!(!LogTester::contains_regex(Level::Info, r"request \d+ took \d+s").unwrap());
+        assert!(LogTester::contains_regex(Level::Info, "(").is_err());
+    }
+
+    #[test]
+    fn test_find_and_assert_matches() {
+        LogTester::start_thread_local();
+        warn!(target: "mycrate::net", "retrying");
+        let found = LogTester::find(|log| log.level == Level::Warn && log.target == "mycrate::net");
+        assert_eq!(found.map(|log| log.body), Some("retrying".to_string()));
+        assert!(LogTester::find(|log| log.level == Level::Error).is_none());
+
+        LogTester::assert_matches(|log| log.level == Level::Warn && log.target == "mycrate::net");
+    }
+
+    #[test]
+    #[should_panic(expected = "No captured log matched the given predicate")]
+    fn test_assert_matches_panics() {
+        LogTester::start_thread_local();
+        LogTester::clear();
+        LogTester::assert_matches(|log| log.level == Level::Error);
+    }
+
+    #[test]
+    fn test_ordered() {
+        LogTester::start_thread_local();
+        LogTester::clear();
+        info!("first");
+        info!("second");
+        info!("third");
+        let logs = LogTester::ordered();
+        assert_eq!(
+            logs.iter().map(|log| log.body.as_str()).collect::<Vec<_>>(),
+            vec!["first", "second", "third"]
+        );
+    }
+
+    #[test]
+    fn test_entries_between() {
+        LogTester::start_thread_local();
+        LogTester::clear();
+        let start = std::time::Instant::now();
+        warn!("retry 1");
+        warn!("retry 2");
+        let end = std::time::Instant::now();
+        assert_eq!(LogTester::entries_between(start, end).len(), 2);
+
+        let before_start = start - std::time::Duration::from_secs(60);
+        assert_eq!(
+            LogTester::entries_between(before_start, start).len(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_display_shows_elapsed() {
+        LogTester::start_thread_local();
+        LogTester::clear();
+        info!("with timestamp");
+        let rendered = format!("{}", LogTester);
+        assert!(rendered.contains("ago] with timestamp"));
+    }
+
+    #[test]
+    fn test_with_context() {
+        LogTester::start_thread_local();
+        LogTester::clear();
+
+        {
+            let _ctx = LogTester::with_context(&[("request_id", "42")]);
+            info!("handling request");
+        }
+        info!("outside any context");
+
+        assert!(LogTester::contains_in_context(
+            "request_id",
+            "42",
+            Level::Info,
+            "handling request"
+        ));
+        assert!(!LogTester::contains_in_context(
+            "request_id",
+            "42",
+            Level::Info,
+            "outside any context"
+        ));
+    }
+
+    #[test]
+    fn test_with_context_nesting_restores_previous() {
+        LogTester::start_thread_local();
+        LogTester::clear();
+
+        let _outer = LogTester::with_context(&[("request_id", "1")]);
+        info!("outer");
+        {
+            let _inner = LogTester::with_context(&[("request_id", "2")]);
+            info!("inner");
+        }
+        info!("outer again");
+
+        assert!(LogTester::contains_in_context(
+            "request_id", "1", Level::Info, "outer"
+        ));
+        assert!(LogTester::contains_in_context(
+            "request_id", "2", Level::Info, "inner"
+        ));
+        assert!(LogTester::contains_in_context(
+            "request_id", "1", Level::Info, "outer again"
+        ));
+    }
+
+    #[test]
+    fn test_with_context_nesting_merges_distinct_keys() {
+        LogTester::start_thread_local();
+        LogTester::clear();
+
+        let _outer = LogTester::with_context(&[("request_id", "42")]);
+        {
+            let _inner = LogTester::with_context(&[("component", "db")]);
+            info!("querying");
+        }
+
+        assert!(LogTester::contains_in_context(
+            "request_id", "42", Level::Info, "querying"
+        ));
+        assert!(LogTester::contains_in_context(
+            "component", "db", Level::Info, "querying"
+        ));
+    }
 }